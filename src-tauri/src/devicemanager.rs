@@ -1,10 +1,11 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, ErrorKind}; // ErrorKind 추가
-use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Manager};
-use uuid::Uuid; 
+use std::io::{BufReader, ErrorKind}; // ErrorKind 추가
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
 
 // 프론트엔드의 Device 인터페이스와 일치하는 Rust 구조체 정의
 // Serialize, Deserialize를 derive하여 JSON 변환 가능하도록 함
@@ -18,13 +19,51 @@ pub struct Device {
     // None일 경우 JSON 직렬화에서 제외
     #[serde(rename = "targetAddr", skip_serializing_if = "Option::is_none")]
     target_addr: Option<String>,
+    // 예약 깨우기를 위한 cron 유사 스펙(예: "0 0 7 * * *"). None이면 예약 없음.
+    // 기존 저장 데이터와의 호환을 위해 default/skip 처리한다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule: Option<String>,
+    // 예약 발화 시 적용할 재시도 정책. None이면 1회만 전송한다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryPolicy>,
 }
 
+// 불안정한 NIC 대비로 매직 패킷을 몇 차례 연달아 보내는 재시도 정책.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    // 전송 횟수(버스트 개수). 1이면 재시도 없음.
+    bursts: u32,
+    // 버스트 사이의 간격(초).
+    #[serde(rename = "intervalSecs")]
+    interval_secs: u64,
+}
+
+// 앱 전역에서 단 하나만 열리는 sled 데이터베이스 핸들.
+// 최초 커맨드 호출 시 앱 설정 디렉토리에 열고, 이후 호출은 이 핸들을 재사용한다.
+// sled::Db / Tree는 내부적으로 Arc 기반이라 clone이 저렴하고 스레드 안전하므로,
+// Tauri 커맨드가 동시에 호출되어도 전체 파일을 다시 쓰던 기존 방식과 달리
+// 장치 단위의 원자적 쓰기가 보장된다.
+static DB: OnceLock<sled::Db> = OnceLock::new();
+
+// DB 최초 열기를 직렬화하는 잠금.
+// OnceLock은 값 설정만 원자적으로 보장할 뿐, 그 전에 실행되는 sled::open 자체는
+// 보호하지 못한다. sled는 파일에 배타 잠금을 걸므로, 동시에 두 스레드가
+// is_none() 검사를 통과해 각각 open을 시도하면 뒤의 호출이 잠금 충돌로 실패한다.
+// 이 뮤텍스로 open~set 구간을 감싸 경쟁하는 호출이 승자의 핸들을 재사용하게 한다.
+static DB_INIT: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+// 장치가 저장되는 트리 이름. 키는 장치 UUID 문자열, 값은 JSON 직렬화된 Device.
+const DEVICES_TREE: &str = "devices";
+
+// 메타데이터 트리 이름. lastModified 같은 스토어 수준 정보를 보관한다.
+const META_TREE: &str = "meta";
+// lastModified(마지막 변경 epoch, 밀리초)를 저장하는 키.
+const LAST_MODIFIED_KEY: &str = "lastModified";
+
 // --- Helper Functions ---
 
-// 설정 디렉토리 내의 devices.json 파일 경로를 가져오는 함수
-// 설정 디렉토리가 없으면 생성함
-fn get_config_path(app_handle: &AppHandle) -> Result<PathBuf> {
+// 앱 설정 디렉토리 경로를 가져오는 함수. 없으면 생성한다.
+fn get_config_dir(app_handle: &AppHandle) -> Result<PathBuf> {
     // app_handle.path().app_config_dir()를 사용하여 앱별 설정 디렉토리 경로 획득
     let config_dir = app_handle
         .path()
@@ -38,106 +77,627 @@ fn get_config_path(app_handle: &AppHandle) -> Result<PathBuf> {
         println!("Config directory created at: {:?}", config_dir); // 생성 로그
     }
 
-    // 설정 디렉토리 내의 devices.json 파일 경로 반환
-    Ok(config_dir.join("devices.json"))
+    Ok(config_dir)
 }
 
-// JSON 파일에서 장치 목록을 읽어오는 함수
-fn read_devices_from_file(path: &Path) -> Result<Vec<Device>> {
-    match File::open(path) {
+// 장치 트리를 반환하는 함수.
+// 최초 호출 시 sled::Db를 열고, 기존 devices.json이 있으면 한 번만 트리로 이관한다.
+fn get_devices_tree(app_handle: &AppHandle) -> Result<sled::Tree> {
+    let config_dir = get_config_dir(app_handle)?;
+
+    // OnceLock은 오류를 담을 수 없으므로, 먼저 열기를 시도한 뒤 set으로 채운다.
+    // open 자체가 경쟁에 안전하지 않으므로(sled 배타 잠금) DB_INIT로 직렬화하고,
+    // 잠금 획득 후 다시 한 번 검사하여 승자가 이미 채웠다면 open을 건너뛴다.
+    if DB.get().is_none() {
+        let _guard = DB_INIT
+            .lock()
+            .map_err(|_| anyhow::anyhow!("DB 초기화 잠금을 획득할 수 없습니다."))?;
+        if DB.get().is_none() {
+            let db = sled::open(config_dir.join("devices.db"))
+                .with_context(|| format!("sled 데이터베이스 열기 실패: {:?}", config_dir))?;
+            let _ = DB.set(db);
+        }
+    }
+
+    let db = DB
+        .get()
+        .context("sled 데이터베이스 핸들을 가져올 수 없습니다.")?;
+    let tree = db
+        .open_tree(DEVICES_TREE)
+        .context("장치 트리를 열 수 없습니다.")?;
+
+    // 최초 1회: 기존 devices.json이 있고 트리가 비어 있으면 이관한다.
+    migrate_json_if_needed(&config_dir, &tree)?;
+
+    Ok(tree)
+}
+
+// 기존 devices.json을 sled 트리로 한 번만 이관하는 마이그레이션.
+// 트리가 이미 비어있지 않으면(= 이관 완료 또는 사용 중) 아무것도 하지 않는다.
+fn migrate_json_if_needed(config_dir: &std::path::Path, tree: &sled::Tree) -> Result<()> {
+    if !tree.is_empty() {
+        return Ok(());
+    }
+
+    let legacy_path = config_dir.join("devices.json");
+    match File::open(&legacy_path) {
         Ok(file) => {
-            // 파일이 존재하면 읽어서 역직렬화
             let reader = BufReader::new(file);
             let devices: Vec<Device> = serde_json::from_reader(reader)
-                .with_context(|| format!("장치 파일 역직렬화 실패: {:?}", path))?;
-            Ok(devices)
+                .with_context(|| format!("장치 파일 역직렬화 실패: {:?}", legacy_path))?;
+            for device in &devices {
+                let bytes = serde_json::to_vec(device)
+                    .with_context(|| format!("장치 직렬화 실패: {}", device.id))?;
+                tree.insert(device.id.as_bytes(), bytes)
+                    .context("장치 트리 이관 쓰기 실패")?;
+            }
+            tree.flush().context("장치 트리 flush 실패")?;
+            // 이관이 끝나면 기존 파일을 백업 용도로 이름만 바꿔 둔다(삭제 대신 보존).
+            let backup = config_dir.join("devices.json.migrated");
+            fs::rename(&legacy_path, &backup)
+                .with_context(|| format!("devices.json 백업 이름 변경 실패: {:?}", backup))?;
+            println!(
+                "Migrated {} device(s) from devices.json into sled tree.",
+                devices.len()
+            );
         }
         Err(error) if error.kind() == ErrorKind::NotFound => {
-            // 파일이 존재하지 않으면 빈 목록 반환 (오류 아님)
-            println!("Device file not found at {:?}, returning empty list.", path);
-            Ok(Vec::new())
+            // 기존 파일이 없으면 이관할 것도 없음 (오류 아님)
         }
         Err(error) => {
-            // 그 외 파일 열기 오류
-            Err(error).with_context(|| format!("장치 파일 열기 실패: {:?}", path))
+            return Err(error).with_context(|| format!("장치 파일 열기 실패: {:?}", legacy_path));
+        }
+    }
+
+    Ok(())
+}
+
+// 트리에 저장된 하나의 값(바이트)을 Device로 역직렬화한다.
+fn decode_device(bytes: &[u8]) -> Result<Device> {
+    serde_json::from_slice(bytes).context("장치 트리 값 역직렬화 실패")
+}
+
+// QR로 주고받을 때 쓰는 최소 페이로드. id는 설치마다 새로 발급되므로 제외하고
+// 장치의 실질 정보(name, mac, targetAddr)만 담는다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DevicePayload {
+    name: String,
+    mac: String,
+    #[serde(rename = "targetAddr", skip_serializing_if = "Option::is_none")]
+    target_addr: Option<String>,
+}
+
+impl From<&Device> for DevicePayload {
+    fn from(device: &Device) -> Self {
+        DevicePayload {
+            name: device.name.clone(),
+            mac: device.mac.clone(),
+            target_addr: device.target_addr.clone(),
+        }
+    }
+}
+
+// 로컬 서브넷 스캔으로 발견된 호스트 하나. 프론트엔드가 픽-리스트로 보여주어
+// Device.mac / targetAddr 입력을 대신 채울 수 있도록 한다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredHost {
+    ip: String,
+    mac: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+    // MAC을 안정적 식별자로 삼아, 이미 저장된 장치인지 교차 확인한 결과
+    saved: bool,
+}
+
+// MAC 주소를 비교용으로 정규화한다(대소문자/구분자 차이를 흡수).
+fn normalize_mac(mac: &str) -> String {
+    mac.to_ascii_lowercase().replace('-', ":")
+}
+
+// 현재 시각을 epoch 밀리초로 반환한다.
+fn now_epoch_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// 메타데이터 트리를 연다.
+fn get_meta_tree(app_handle: &AppHandle) -> Result<sled::Tree> {
+    // get_devices_tree가 DB를 초기화하므로 먼저 호출하여 핸들을 보장한다.
+    let _ = get_devices_tree(app_handle)?;
+    let db = DB
+        .get()
+        .context("sled 데이터베이스 핸들을 가져올 수 없습니다.")?;
+    db.open_tree(META_TREE).context("메타데이터 트리를 열 수 없습니다.")
+}
+
+// 스토어의 lastModified 값을 읽는다(없으면 0).
+fn read_last_modified(app_handle: &AppHandle) -> Result<u64> {
+    let meta = get_meta_tree(app_handle)?;
+    match meta.get(LAST_MODIFIED_KEY).context("lastModified 조회 실패")? {
+        Some(bytes) => {
+            let s = std::str::from_utf8(&bytes).context("lastModified 디코딩 실패")?;
+            Ok(s.parse().unwrap_or(0))
         }
+        None => Ok(0),
     }
 }
 
-// 장치 목록을 JSON 파일에 쓰는 함수 (덮어쓰기)
-fn write_devices_to_file(path: &Path, devices: &[Device]) -> Result<()> {
-    // 파일을 생성하거나 열어서 쓰기 준비 (기존 내용 삭제)
-    let file = File::create(path)
-        .with_context(|| format!("장치 파일 생성/열기 실패: {:?}", path))?;
-    let writer = BufWriter::new(file);
-    // JSON 형식으로 직렬화하여 파일에 쓰기 (pretty print로 가독성 높임)
-    serde_json::to_writer_pretty(writer, devices)
-        .with_context(|| format!("장치 파일 직렬화 실패: {:?}", path))?;
+// lastModified를 주어진 값으로 설정한다.
+fn set_last_modified(app_handle: &AppHandle, ts: u64) -> Result<()> {
+    let meta = get_meta_tree(app_handle)?;
+    meta.insert(LAST_MODIFIED_KEY, ts.to_string().as_bytes())
+        .context("lastModified 쓰기 실패")?;
+    meta.flush().context("메타데이터 트리 flush 실패")?;
     Ok(())
 }
 
+// 장치가 변경될 때마다 lastModified를 현재 시각으로 갱신한다.
+fn touch_last_modified(app_handle: &AppHandle) -> Result<()> {
+    set_last_modified(app_handle, now_epoch_ms())
+}
+
+// 트리에 저장된 모든 장치를 모아 반환한다.
+fn collect_devices(tree: &sled::Tree) -> Result<Vec<Device>> {
+    tree.iter()
+        .map(|entry| {
+            let (_key, value) = entry.context("장치 트리 순회 실패")?;
+            decode_device(&value)
+        })
+        .collect()
+}
+
 // --- Internal Logic Functions (using anyhow::Result) ---
 // 내부 로직 함수들은 anyhow::Result를 반환하여 에러 처리를 용이하게 함
 
 async fn load_devices_internal(app_handle: AppHandle) -> Result<Vec<Device>> {
-    let path = get_config_path(&app_handle)?;
-    println!("Reading devices from: {:?}", path);
-    read_devices_from_file(&path)
+    let tree = get_devices_tree(&app_handle)?;
+    println!("Reading devices from sled tree...");
+    collect_devices(&tree)
 }
 
-async fn add_device_internal(app_handle: AppHandle, mut new_device_data: Device) -> Result<Vec<Device>> {
-    let path = get_config_path(&app_handle)?;
-    let mut devices = read_devices_from_file(&path)?;
+async fn add_device_internal(
+    app_handle: AppHandle,
+    mut new_device_data: Device,
+) -> Result<Vec<Device>> {
+    let tree = get_devices_tree(&app_handle)?;
 
     // 새 장치의 ID 생성 (기존 ID가 있더라도 덮어씀)
     new_device_data.id = Uuid::new_v4().to_string();
     println!("Generated new device ID: {}", new_device_data.id);
 
     // TODO: 필요시 중복 검사 (예: 동일 MAC 주소)
-    // if devices.iter().any(|d| d.mac == new_device_data.mac) {
+    // if collect_devices(&tree)?.iter().any(|d| d.mac == new_device_data.mac) {
     //     anyhow::bail!("Device with MAC {} already exists", new_device_data.mac);
     // }
 
-    devices.push(new_device_data); // 목록에 새 장치 추가
-    write_devices_to_file(&path, &devices)?; // 변경된 목록을 파일에 저장
+    let bytes = serde_json::to_vec(&new_device_data)
+        .with_context(|| format!("장치 직렬화 실패: {}", new_device_data.id))?;
+    // 장치 단위 원자적 쓰기 — 전체 목록을 다시 쓰지 않는다.
+    tree.insert(new_device_data.id.as_bytes(), bytes)
+        .context("장치 추가 쓰기 실패")?;
+    tree.flush().context("장치 트리 flush 실패")?;
+    touch_last_modified(&app_handle)?;
+
+    let devices = collect_devices(&tree)?;
     println!("Device added. Total devices: {}", devices.len());
     Ok(devices) // 업데이트된 전체 장치 목록 반환
 }
 
-async fn update_device_internal(app_handle: AppHandle, updated_device: Device) -> Result<Vec<Device>> {
-    let path = get_config_path(&app_handle)?;
-    let mut devices = read_devices_from_file(&path)?;
+async fn update_device_internal(
+    app_handle: AppHandle,
+    updated_device: Device,
+) -> Result<Vec<Device>> {
+    let tree = get_devices_tree(&app_handle)?;
 
-    // 주어진 ID와 일치하는 장치의 인덱스 찾기
-    if let Some(index) = devices.iter().position(|d| d.id == updated_device.id) {
-        println!("Updating device with ID: {}", updated_device.id);
-        devices[index] = updated_device; // 찾은 위치의 장치 정보 업데이트
-        write_devices_to_file(&path, &devices)?; // 변경된 목록을 파일에 저장
-        Ok(devices) // 업데이트된 전체 장치 목록 반환
-    } else {
-        // 해당 ID의 장치를 찾지 못한 경우 에러 반환
-        anyhow::bail!("ID '{}'를 가진 장치를 찾을 수 없어 업데이트할 수 없습니다.", updated_device.id)
+    // 주어진 ID의 장치가 존재하는지 먼저 확인
+    if !tree
+        .contains_key(updated_device.id.as_bytes())
+        .context("장치 존재 여부 확인 실패")?
+    {
+        anyhow::bail!(
+            "ID '{}'를 가진 장치를 찾을 수 없어 업데이트할 수 없습니다.",
+            updated_device.id
+        );
     }
+
+    println!("Updating device with ID: {}", updated_device.id);
+    let bytes = serde_json::to_vec(&updated_device)
+        .with_context(|| format!("장치 직렬화 실패: {}", updated_device.id))?;
+    tree.insert(updated_device.id.as_bytes(), bytes)
+        .context("장치 업데이트 쓰기 실패")?;
+    tree.flush().context("장치 트리 flush 실패")?;
+    touch_last_modified(&app_handle)?;
+
+    collect_devices(&tree)
 }
 
 async fn delete_device_internal(app_handle: AppHandle, device_id: String) -> Result<Vec<Device>> {
-    let path = get_config_path(&app_handle)?;
-    let mut devices = read_devices_from_file(&path)?;
-
-    let initial_len = devices.len();
-    // 주어진 ID와 일치하지 않는 장치만 남기고 목록 필터링
-    devices.retain(|d| d.id != device_id);
+    let tree = get_devices_tree(&app_handle)?;
 
-    // 삭제된 장치가 있는지 확인 (목록 길이가 줄었는지)
-    if devices.len() == initial_len {
+    // remove는 제거된 이전 값을 반환한다. None이면 해당 ID가 없었던 것.
+    let removed = tree
+        .remove(device_id.as_bytes())
+        .context("장치 삭제 실패")?;
+    if removed.is_none() {
         anyhow::bail!("ID '{}'를 가진 장치를 찾을 수 없어 삭제할 수 없습니다.", device_id);
     }
+    tree.flush().context("장치 트리 flush 실패")?;
+    touch_last_modified(&app_handle)?;
 
     println!("Device with ID {} deleted.", device_id);
-    write_devices_to_file(&path, &devices)?; // 변경된 목록을 파일에 저장
-    Ok(devices) // 업데이트된 전체 장치 목록 반환
+    collect_devices(&tree)
+}
+
+async fn set_device_schedule_internal(
+    app_handle: AppHandle,
+    device_id: String,
+    spec: Option<String>,
+) -> Result<Vec<Device>> {
+    let tree = get_devices_tree(&app_handle)?;
+
+    let bytes = tree
+        .get(device_id.as_bytes())
+        .context("장치 조회 실패")?
+        .with_context(|| format!("ID '{}'를 가진 장치를 찾을 수 없습니다.", device_id))?;
+    let mut device = decode_device(&bytes)?;
+
+    // 빈 문자열은 예약 해제로 간주한다.
+    device.schedule = spec.filter(|s| !s.trim().is_empty());
+    println!("Setting schedule for {} to {:?}", device_id, device.schedule);
+
+    // 기존 업데이트 경로를 통과시켜 lastModified 갱신까지 일관되게 처리한다.
+    update_device_internal(app_handle, device).await
+}
+
+// wol://scheduled-fired 이벤트 페이로드.
+#[derive(Debug, Clone, Serialize)]
+struct ScheduledFired {
+    #[serde(rename = "deviceId")]
+    device_id: String,
+    name: String,
+    // 발화 시각(epoch ms)
+    timestamp: u64,
+}
+
+// 예약에 따라 한 장치를 깨운다. 재시도 정책이 있으면 버스트만큼 간격을 두고 반복 전송한다.
+async fn fire_scheduled_wake(app_handle: &AppHandle, device: &Device) {
+    let (bursts, interval) = match &device.retry {
+        Some(policy) => (policy.bursts.max(1), policy.interval_secs),
+        None => (1, 0),
+    };
+
+    for i in 0..bursts {
+        if let Err(e) =
+            crate::wol::send_wol_packet_internal(device.mac.clone(), device.target_addr.clone())
+                .await
+        {
+            eprintln!("Scheduled wake failed for {}: {:?}", device.id, e);
+        }
+        // 마지막 버스트 이후에는 대기하지 않는다.
+        if i + 1 < bursts && interval > 0 {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+
+    if let Err(e) = app_handle.emit(
+        "wol://scheduled-fired",
+        ScheduledFired {
+            device_id: device.id.clone(),
+            name: device.name.clone(),
+            timestamp: now_epoch_ms(),
+        },
+    ) {
+        eprintln!("Failed to emit scheduled-fired event: {:?}", e);
+    }
+}
+
+// run()에서 스폰되는 백그라운드 예약 태스크.
+// 1분 간격으로 저장된 장치의 cron 스펙을 평가하여, 직전 tick 이후 도래한 예약이 있으면 깨운다.
+pub fn start_scheduler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        use cron::Schedule;
+        use std::str::FromStr;
+
+        // 이전 점검 시각. 최초에는 현재 시각으로 두어 과거 예약을 소급 발화하지 않는다.
+        // cron 스펙은 사용자 기대에 맞춰 로컬 시간대로 평가한다("0 0 7 * * *" → 로컬 07:00).
+        let mut last_tick = chrono::Local::now();
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+
+        loop {
+            interval.tick().await;
+            let now = chrono::Local::now();
+
+            // 현재 저장된 장치를 읽어온다. 실패하면 이번 tick은 건너뛴다.
+            let tree = match get_devices_tree(&app_handle) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    eprintln!("Scheduler: failed to open device tree: {:?}", e);
+                    last_tick = now;
+                    continue;
+                }
+            };
+            let devices = match collect_devices(&tree) {
+                Ok(devices) => devices,
+                Err(e) => {
+                    eprintln!("Scheduler: failed to read devices: {:?}", e);
+                    last_tick = now;
+                    continue;
+                }
+            };
+
+            for device in &devices {
+                let Some(spec) = &device.schedule else { continue };
+                let schedule = match Schedule::from_str(spec) {
+                    Ok(schedule) => schedule,
+                    Err(e) => {
+                        eprintln!("Scheduler: invalid cron spec '{}': {:?}", spec, e);
+                        continue;
+                    }
+                };
+                // 직전 tick 이후 now까지 사이에 도래한 예약이 하나라도 있으면 발화한다.
+                if let Some(next) = schedule.after(&last_tick).next() {
+                    if next <= now {
+                        println!("Scheduler: firing wake for device {}", device.id);
+                        fire_scheduled_wake(&app_handle, device).await;
+                    }
+                }
+            }
+
+            last_tick = now;
+        }
+    });
+}
+
+// 내보내기/가져오기에 쓰는 내부 스냅샷. rawDeviceList 문자열 안에 담긴다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DeviceListSnapshot {
+    devices: Vec<Device>,
+    // 스토어의 lastModified(epoch ms)
+    timestamp: u64,
+}
+
+// 버전드 봉투. rawDeviceList는 DeviceListSnapshot의 JSON 문자열이고,
+// signature는 rawDeviceList에 대한 HMAC-SHA256 서명(16진수)이다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportEnvelope {
+    #[serde(rename = "rawDeviceList")]
+    raw_device_list: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+// rawDeviceList 서명에 쓰는 앱 공유 키. 서로 다른 설치본이 주고받은 목록을
+// 상호 검증할 수 있도록(변조 탐지 목적) 애플리케이션에 고정 내장한다.
+const DEVICE_LIST_SIGNING_KEY: &[u8] = b"wolc-device-list-signing-key-v1";
+
+// rawDeviceList 문자열에 대한 HMAC-SHA256 서명을 16진수 문자열로 계산한다.
+fn sign_device_list(raw: &str) -> Result<String> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    let mut mac = Hmac::<Sha256>::new_from_slice(DEVICE_LIST_SIGNING_KEY)
+        .context("서명 키 초기화 실패")?;
+    mac.update(raw.as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+// 주어진 rawDeviceList와 서명이 일치하는지 상수 시간 비교로 검증한다.
+fn verify_device_list(raw: &str, signature: &str) -> Result<()> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    let mut mac = Hmac::<Sha256>::new_from_slice(DEVICE_LIST_SIGNING_KEY)
+        .context("서명 키 초기화 실패")?;
+    mac.update(raw.as_bytes());
+    let expected = hex::decode(signature).context("서명 16진수 디코딩 실패")?;
+    mac.verify_slice(&expected)
+        .map_err(|_| anyhow::anyhow!("장치 목록 서명이 일치하지 않습니다."))
+}
+
+async fn export_device_list_internal(app_handle: AppHandle) -> Result<String> {
+    let tree = get_devices_tree(&app_handle)?;
+    let devices = collect_devices(&tree)?;
+    let timestamp = read_last_modified(&app_handle)?;
+
+    let snapshot = DeviceListSnapshot { devices, timestamp };
+    let raw = serde_json::to_string(&snapshot).context("장치 목록 스냅샷 직렬화 실패")?;
+    let signature = sign_device_list(&raw)?;
+    let envelope = ExportEnvelope {
+        raw_device_list: raw,
+        signature: Some(signature),
+    };
+    serde_json::to_string(&envelope).context("내보내기 봉투 직렬화 실패")
+}
+
+// 트리를 주어진 장치 목록으로 완전히 대체한다.
+fn replace_all_devices(tree: &sled::Tree, devices: &[Device]) -> Result<()> {
+    tree.clear().context("장치 트리 초기화 실패")?;
+    for device in devices {
+        let bytes = serde_json::to_vec(device)
+            .with_context(|| format!("장치 직렬화 실패: {}", device.id))?;
+        tree.insert(device.id.as_bytes(), bytes)
+            .context("장치 트리 쓰기 실패")?;
+    }
+    tree.flush().context("장치 트리 flush 실패")?;
+    Ok(())
+}
+
+async fn import_device_list_internal(app_handle: AppHandle, payload: String) -> Result<Vec<Device>> {
+    let envelope: ExportEnvelope =
+        serde_json::from_str(&payload).context("내보내기 봉투 역직렬화 실패")?;
+    // signature가 있으면 rawDeviceList와 대조해 변조 여부를 검증한다.
+    // 서명이 있으나 맞지 않으면 가져오기를 거부한다(서명이 아예 없으면 하위 호환으로 통과).
+    if let Some(signature) = &envelope.signature {
+        verify_device_list(&envelope.raw_device_list, signature)?;
+    }
+    let incoming: DeviceListSnapshot =
+        serde_json::from_str(&envelope.raw_device_list).context("장치 목록 스냅샷 역직렬화 실패")?;
+
+    let tree = get_devices_tree(&app_handle)?;
+    let local_ts = read_last_modified(&app_handle)?;
+
+    use std::cmp::Ordering;
+    match incoming.timestamp.cmp(&local_ts) {
+        Ordering::Greater => {
+            // 들어온 목록이 더 최신 → 로컬을 대체한다.
+            println!("Import: incoming list is newer, replacing local store.");
+            replace_all_devices(&tree, &incoming.devices)?;
+            set_last_modified(&app_handle, incoming.timestamp)?;
+        }
+        Ordering::Less => {
+            // 들어온 목록이 더 오래됨 → 거부한다.
+            anyhow::bail!(
+                "가져온 장치 목록이 로컬보다 오래되어(로컬 {}, 가져옴 {}) 적용하지 않았습니다.",
+                local_ts,
+                incoming.timestamp
+            );
+        }
+        Ordering::Equal => {
+            // 동시(동일 타임스탬프) → UUID 기준으로 병합한다.
+            // 두 머신이 서로를 가져와도 같은 결과로 수렴하도록, id 충돌 시에는
+            // 직렬화 바이트가 사전순으로 큰 항목을 결정적으로 선택한다.
+            println!("Import: concurrent timestamp, merging by device id.");
+            let mut merged: std::collections::BTreeMap<String, Device> =
+                std::collections::BTreeMap::new();
+            for device in collect_devices(&tree)?.into_iter().chain(incoming.devices.into_iter()) {
+                match merged.get(&device.id) {
+                    Some(existing) => {
+                        let existing_json = serde_json::to_string(existing).unwrap_or_default();
+                        let candidate_json = serde_json::to_string(&device).unwrap_or_default();
+                        if candidate_json > existing_json {
+                            merged.insert(device.id.clone(), device);
+                        }
+                    }
+                    None => {
+                        merged.insert(device.id.clone(), device);
+                    }
+                }
+            }
+            let devices: Vec<Device> = merged.into_values().collect();
+            replace_all_devices(&tree, &devices)?;
+            // 병합 결과를 새 변경으로 간주하여 lastModified를 갱신한다.
+            touch_last_modified(&app_handle)?;
+        }
+    }
+
+    collect_devices(&tree)
+}
+
+// 커널 ARP 캐시에서 (ip, mac) 쌍을 읽는다.
+// 주의: 이 구현은 능동적 ARP 스윕이 아니라 *수동적 캐시 덤프*다. 커널이 최근 통신으로
+// 이미 학습한 항목만 보이므로, 한 번도 통신한 적 없는 호스트는 나타나지 않는다.
+// (UI에서 "최근에 본 호스트" 정도로 다루는 것이 옳다.)
+// Linux에서만 /proc/net/arp로 구현하며, 다른 데스크톱 타깃(macOS/Windows)에서는
+// 해당 파일이 없으므로 지원되지 않음을 명확히 알린다.
+#[cfg(target_os = "linux")]
+fn read_arp_cache() -> Result<Vec<(String, String)>> {
+    let arp = fs::read_to_string("/proc/net/arp")
+        .context("ARP 캐시(/proc/net/arp)를 읽을 수 없습니다.")?;
+
+    let mut pairs = Vec::new();
+    // 첫 줄은 헤더이므로 건너뛴다.
+    // 형식: IP address  HW type  Flags  HW address  Mask  Device
+    for line in arp.lines().skip(1) {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() < 4 {
+            continue;
+        }
+        let ip = cols[0].to_string();
+        let mac = cols[3].to_string();
+        // 미완성 항목(00:00:00:00:00:00)은 아직 MAC을 모르는 것이므로 제외한다.
+        if mac == "00:00:00:00:00:00" {
+            continue;
+        }
+        pairs.push((ip, mac));
+    }
+    Ok(pairs)
+}
+
+// Linux 외 플랫폼에는 /proc/net/arp가 없으므로 현재 지원 범위를 명확히 밝힌다.
+#[cfg(not(target_os = "linux"))]
+fn read_arp_cache() -> Result<Vec<(String, String)>> {
+    anyhow::bail!("네트워크 검색은 현재 Linux에서만 지원됩니다(ARP 캐시 기반).")
 }
 
+// 로컬 네트워크의 호스트를 수집하는 내부 로직.
+// 능동적 ARP 스윕 대신 커널이 이미 유지하는 ARP 캐시를 읽어 최근 통신한 호스트를
+// 열거한다(플랫폼별 세부는 read_arp_cache 참고). 별도의 raw 소켓 권한 없이
+// ip/mac 쌍을 얻을 수 있으나, 캐시에 없는 호스트는 보이지 않는 한계가 있다.
+async fn scan_network_internal(app_handle: AppHandle) -> Result<Vec<DiscoveredHost>> {
+    // 저장된 장치들의 MAC 집합을 만들어 교차 확인에 사용한다.
+    let tree = get_devices_tree(&app_handle)?;
+    let saved_macs: std::collections::HashSet<String> = collect_devices(&tree)?
+        .iter()
+        .map(|d| normalize_mac(&d.mac))
+        .collect();
+
+    let mut hosts = Vec::new();
+    for (ip, mac) in read_arp_cache()? {
+        // 역방향 DNS로 호스트명을 보조적으로 조회한다(실패해도 무시).
+        let hostname = ip
+            .parse::<std::net::IpAddr>()
+            .ok()
+            .and_then(|addr| dns_lookup::lookup_addr(&addr).ok());
+
+        let saved = saved_macs.contains(&normalize_mac(&mac));
+        hosts.push(DiscoveredHost { ip, mac, hostname, saved });
+    }
+
+    println!("Network scan found {} host(s).", hosts.len());
+    Ok(hosts)
+}
+
+async fn export_device_qr_internal(app_handle: AppHandle, device_id: String) -> Result<String> {
+    let tree = get_devices_tree(&app_handle)?;
+
+    // 대상 장치를 트리에서 찾는다.
+    let bytes = tree
+        .get(device_id.as_bytes())
+        .context("장치 조회 실패")?
+        .with_context(|| format!("ID '{}'를 가진 장치를 찾을 수 없습니다.", device_id))?;
+    let device = decode_device(&bytes)?;
+
+    // name/mac/targetAddr만 담은 compact JSON으로 직렬화한다.
+    let payload = serde_json::to_string(&DevicePayload::from(&device))
+        .context("QR 페이로드 직렬화 실패")?;
+
+    // qrcode 크레이트로 QR 코드를 만들고 SVG 마크업으로 렌더링한다.
+    let code = qrcode::QrCode::new(payload.as_bytes())
+        .context("QR 코드 생성 실패")?;
+    let svg = code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(256, 256)
+        .build();
+
+    // SVG 마크업을 base64 data URI로 감싸 반환한다. 그래야 프론트엔드가
+    // <img src="data:image/svg+xml;base64,..."> 형태로 곧바로 표시할 수 있다.
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(svg.as_bytes());
+    Ok(format!("data:image/svg+xml;base64,{}", encoded))
+}
+
+async fn import_device_from_payload_internal(
+    app_handle: AppHandle,
+    payload: String,
+) -> Result<Vec<Device>> {
+    // 스캔된 JSON을 파싱한다.
+    let parsed: DevicePayload =
+        serde_json::from_str(&payload).context("QR 페이로드 역직렬화 실패")?;
+
+    // 새 장치를 구성한다. id는 기존 add 경로에서 새 UUID로 다시 채워지므로 비워 둔다.
+    let device = Device {
+        id: String::new(),
+        name: parsed.name,
+        mac: parsed.mac,
+        target_addr: parsed.target_addr,
+        // QR 페이로드에는 예약 정보가 없으므로 기본값으로 둔다.
+        schedule: None,
+        retry: None,
+    };
+
+    // 기존 추가 경로를 그대로 통과시켜 UUID 발급과 트리 삽입을 일관되게 처리한다.
+    add_device_internal(app_handle, device).await
+}
 
 // --- Tauri Commands ---
 // Tauri 커맨드 함수들은 Result<T, String>을 반환하여 프론트엔드로 결과를 전달
@@ -181,3 +741,81 @@ pub async fn delete_device(app_handle: AppHandle, device_id: String) -> Result<V
         e.to_string()
     })
 }
+
+#[tauri::command]
+pub async fn set_device_schedule(
+    app_handle: AppHandle,
+    device_id: String,
+    spec: Option<String>,
+) -> Result<Vec<Device>, String> {
+    // 장치에 예약(cron 스펙)을 설정하거나(None/빈 문자열이면) 해제한다.
+    println!("Executing set_device_schedule command for ID: {}", device_id); // 로그 추가
+    set_device_schedule_internal(app_handle, device_id, spec)
+        .await
+        .map_err(|e| {
+            eprintln!("Error setting device schedule: {:?}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn export_device_list(app_handle: AppHandle) -> Result<String, String> {
+    // 서명(선택)과 타임스탬프가 포함된 봉투로 장치 목록을 내보낸다.
+    println!("Executing export_device_list command..."); // 로그 추가
+    export_device_list_internal(app_handle).await.map_err(|e| {
+        eprintln!("Error exporting device list: {:?}", e);
+        e.to_string()
+    })
+}
+
+#[tauri::command]
+pub async fn import_device_list(
+    app_handle: AppHandle,
+    payload: String,
+) -> Result<Vec<Device>, String> {
+    // 봉투를 가져와 타임스탬프 기반 충돌 해결 후 반영한다.
+    println!("Executing import_device_list command..."); // 로그 추가
+    import_device_list_internal(app_handle, payload)
+        .await
+        .map_err(|e| {
+            eprintln!("Error importing device list: {:?}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn scan_network(app_handle: AppHandle) -> Result<Vec<DiscoveredHost>, String> {
+    // 로컬 서브넷을 훑어 발견된 호스트 목록을 반환한다.
+    println!("Executing scan_network command..."); // 로그 추가
+    scan_network_internal(app_handle).await.map_err(|e| {
+        eprintln!("Error scanning network: {:?}", e);
+        e.to_string()
+    })
+}
+
+#[tauri::command]
+pub async fn export_device_qr(app_handle: AppHandle, device_id: String) -> Result<String, String> {
+    // 저장된 장치를 QR 코드(SVG)로 내보낸다.
+    println!("Executing export_device_qr command for ID: {}", device_id); // 로그 추가
+    export_device_qr_internal(app_handle, device_id)
+        .await
+        .map_err(|e| {
+            eprintln!("Error exporting device QR: {:?}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn import_device_from_payload(
+    app_handle: AppHandle,
+    payload: String,
+) -> Result<Vec<Device>, String> {
+    // 스캔된 QR 페이로드로부터 장치를 가져와 추가한다.
+    println!("Executing import_device_from_payload command..."); // 로그 추가
+    import_device_from_payload_internal(app_handle, payload)
+        .await
+        .map_err(|e| {
+            eprintln!("Error importing device from payload: {:?}", e);
+            e.to_string()
+        })
+}
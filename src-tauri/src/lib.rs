@@ -6,14 +6,27 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // 예약 깨우기 백그라운드 태스크를 기동한다.
+            devicemanager::start_scheduler(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // 기존 커맨드
             wol::send_wol_packet,
+            wol::send_wol_packet_debug,
+            wol::wake_and_wait,
             // 새로 추가된 장치 관리 커맨드
             devicemanager::load_devices,
             devicemanager::add_device,
             devicemanager::update_device,
-            devicemanager::delete_device
+            devicemanager::delete_device,
+            devicemanager::export_device_qr,
+            devicemanager::import_device_from_payload,
+            devicemanager::scan_network,
+            devicemanager::export_device_list,
+            devicemanager::import_device_list,
+            devicemanager::set_device_schedule
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,10 +1,35 @@
 use anyhow::{Context, Result}; // anyhow의 Context와 Result를 가져옵니다.
+use serde::Serialize;
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
 use wakey; // wakey 크레이트 사용
 
+// 깨우기 시도 결과. 프론트엔드로 직렬화되어 전달된다.
+#[derive(Debug, Clone, Serialize)]
+pub struct WakeResult {
+    // 타임아웃 전에 대상이 응답(TCP 연결 성공)했는지 여부
+    woke: bool,
+    // 패킷 전송 시점부터 최종 판정까지 걸린 시간(밀리초)
+    elapsed_ms: u128,
+}
+
+// 프로브 진행 상황 이벤트(wol://probe-progress)의 페이로드.
+#[derive(Debug, Clone, Serialize)]
+struct ProbeProgress {
+    // 전송 시점부터의 경과 시간(밀리초)
+    elapsed_ms: u128,
+    // 직전 연결 시도가 성공했는지 여부
+    reachable: bool,
+}
+
 // 내부 로직을 처리하는 별도의 비동기 함수입니다.
 // 이 함수는 anyhow::Result를 반환하여 '?' 연산자를 통한 간결한 오류 처리를 가능하게 합니다.
 // broadcast_addr -> target_addr 로 파라미터 이름 변경
-async fn send_wol_packet_internal(mac_address: String, target_addr: Option<String>) -> Result<()> {
+pub(crate) async fn send_wol_packet_internal(
+    mac_address: String,
+    target_addr: Option<String>,
+) -> Result<()> {
     println!(
         "Attempting to send WOL packet to MAC: {} via target: {:?}", // 로그 메시지 업데이트
         mac_address,
@@ -38,6 +63,235 @@ async fn send_wol_packet_internal(mac_address: String, target_addr: Option<Strin
     Ok(()) // 성공 시 Ok 반환
 }
 
+// 매직 패킷을 보낸 뒤, 대상이 실제로 깨어났는지 TCP 연결을 폴링하여 확인하는 내부 로직.
+// check_addr:port 로 약 1초 간격으로 연결을 시도하며, 성공하면 woke=true로 즉시 종료하고
+// timeout_secs가 지나면 woke=false로 종료한다. 각 시도 후 wol://probe-progress 이벤트를 emit한다.
+async fn wake_and_wait_internal(
+    app_handle: &AppHandle,
+    mac_address: String,
+    target_addr: Option<String>,
+    check_addr: String,
+    port: u16,
+    timeout_secs: u64,
+) -> Result<WakeResult> {
+    // 1. 먼저 매직 패킷을 전송한다.
+    send_wol_packet_internal(mac_address, target_addr).await?;
+
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(timeout_secs);
+    let probe_socket = format!("{}:{}", check_addr, port);
+
+    // 2. 타임아웃까지 약 1초 간격으로 TCP 연결을 시도한다.
+    loop {
+        // 한 번의 연결 시도는 최대 1초까지만 기다린다(응답 없는 호스트에서 멈추지 않도록).
+        let reachable = matches!(
+            tokio::time::timeout(
+                Duration::from_secs(1),
+                tokio::net::TcpStream::connect(&probe_socket),
+            )
+            .await,
+            Ok(Ok(_))
+        );
+
+        let elapsed_ms = started.elapsed().as_millis();
+        // 진행 상황 이벤트 emit (실패는 로그만 남기고 폴링은 계속한다).
+        if let Err(e) = app_handle.emit("wol://probe-progress", ProbeProgress { elapsed_ms, reachable })
+        {
+            eprintln!("Failed to emit probe-progress event: {:?}", e);
+        }
+
+        if reachable {
+            println!("Probe succeeded after {} ms", elapsed_ms);
+            return Ok(WakeResult { woke: true, elapsed_ms });
+        }
+
+        if Instant::now() >= deadline {
+            println!("Probe timed out after {} ms", elapsed_ms);
+            return Ok(WakeResult { woke: false, elapsed_ms });
+        }
+
+        // 다음 시도까지 대기(연결 시도가 즉시 실패한 경우 과도한 재시도를 막는다).
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+// MAC 주소 문자열("AA:BB:CC:DD:EE:FF")을 6바이트로 파싱한다.
+fn parse_mac_bytes(mac_address: &str) -> Result<[u8; 6]> {
+    let parts: Vec<&str> = mac_address.split(':').collect();
+    if parts.len() != 6 {
+        anyhow::bail!("잘못된 MAC 주소 형식입니다: '{}'", mac_address);
+    }
+    let mut bytes = [0u8; 6];
+    for (i, part) in parts.iter().enumerate() {
+        bytes[i] = u8::from_str_radix(part, 16)
+            .with_context(|| format!("MAC 옥텟 파싱 실패: '{}'", part))?;
+    }
+    Ok(bytes)
+}
+
+// WOL 매직 패킷(102바이트)을 구성한다: 0xFF 6바이트 + 대상 MAC 16회 반복.
+fn build_magic_packet(mac: &[u8; 6]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(102);
+    packet.extend_from_slice(&[0xFF; 6]);
+    for _ in 0..16 {
+        packet.extend_from_slice(mac);
+    }
+    packet
+}
+
+// 매직 패킷 위에 합성 Ethernet/IPv4/UDP 헤더를 붙여 Wireshark가 해석 가능한
+// 링크 계층 프레임을 만든다. 실제로 선로에 나간 바이트는 아니지만(전송은 wakey가 담당),
+// 목적지/페이로드를 캡처에서 확인할 수 있게 한다.
+fn build_synthetic_frame(mac: &[u8; 6], dst_ip: [u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::new();
+
+    // Ethernet: 목적지(브로드캐스트), 출발지(대상 MAC를 자리표시자로 사용), EtherType=IPv4
+    frame.extend_from_slice(&[0xFF; 6]);
+    frame.extend_from_slice(mac);
+    frame.extend_from_slice(&[0x08, 0x00]);
+
+    // IPv4 헤더 (20바이트)
+    let udp_len = 8 + payload.len();
+    let ip_total_len = 20 + udp_len;
+    let mut ip = Vec::with_capacity(20);
+    ip.push(0x45); // version 4, IHL 5
+    ip.push(0x00); // DSCP/ECN
+    ip.extend_from_slice(&(ip_total_len as u16).to_be_bytes());
+    ip.extend_from_slice(&[0x00, 0x00]); // identification
+    ip.extend_from_slice(&[0x00, 0x00]); // flags/fragment offset
+    ip.push(0x40); // TTL 64
+    ip.push(0x11); // protocol UDP(17)
+    ip.extend_from_slice(&[0x00, 0x00]); // checksum(0 = 미계산)
+    ip.extend_from_slice(&[0, 0, 0, 0]); // source 0.0.0.0
+    ip.extend_from_slice(&dst_ip);
+    frame.extend_from_slice(&ip);
+
+    // UDP 헤더 (8바이트), WOL 표준 포트 9
+    frame.extend_from_slice(&0u16.to_be_bytes()); // source port
+    frame.extend_from_slice(&9u16.to_be_bytes()); // dest port
+    frame.extend_from_slice(&(udp_len as u16).to_be_bytes());
+    frame.extend_from_slice(&[0x00, 0x00]); // checksum(0 = 미계산)
+
+    // 페이로드(매직 패킷)
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// pcapng 블록을 32비트 경계에 맞춰 패딩할 바이트 수를 구한다.
+fn pad_to_32(len: usize) -> usize {
+    (4 - (len % 4)) % 4
+}
+
+// pcapng 파일 선두의 고정 헤더(Section Header Block + Interface Description Block)를 만든다.
+// 파일을 처음 만들 때 한 번만 기록하고, 이후 전송마다 Enhanced Packet Block만 덧붙인다.
+fn build_pcapng_header() -> Vec<u8> {
+    let mut out = Vec::new();
+
+    // --- Section Header Block ---
+    out.extend_from_slice(&0x0A0D_0D0Au32.to_le_bytes()); // block type
+    out.extend_from_slice(&28u32.to_le_bytes()); // total length
+    out.extend_from_slice(&0x1A2B_3C4Du32.to_le_bytes()); // byte-order magic
+    out.extend_from_slice(&1u16.to_le_bytes()); // major version
+    out.extend_from_slice(&0u16.to_le_bytes()); // minor version
+    out.extend_from_slice(&(-1i64).to_le_bytes()); // section length (unknown)
+    out.extend_from_slice(&28u32.to_le_bytes()); // total length (trailer)
+
+    // --- Interface Description Block ---
+    out.extend_from_slice(&0x0000_0001u32.to_le_bytes()); // block type
+    out.extend_from_slice(&20u32.to_le_bytes()); // total length
+    out.extend_from_slice(&1u16.to_le_bytes()); // LINKTYPE_ETHERNET
+    out.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    out.extend_from_slice(&0u32.to_le_bytes()); // snaplen (0 = no limit)
+    out.extend_from_slice(&20u32.to_le_bytes()); // total length (trailer)
+
+    out
+}
+
+// 프레임 하나를 담은 Enhanced Packet Block(마이크로초 타임스탬프)을 만든다.
+fn build_enhanced_packet_block(frame: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0);
+    let ts_high = (micros >> 32) as u32;
+    let ts_low = (micros & 0xFFFF_FFFF) as u32;
+    let cap_len = frame.len();
+    let pad = pad_to_32(cap_len);
+    let epb_total = 32 + cap_len + pad; // 헤더/트레일러 고정 32바이트 + 패딩된 데이터
+
+    out.extend_from_slice(&0x0000_0006u32.to_le_bytes()); // block type
+    out.extend_from_slice(&(epb_total as u32).to_le_bytes()); // total length
+    out.extend_from_slice(&0u32.to_le_bytes()); // interface id
+    out.extend_from_slice(&ts_high.to_le_bytes()); // timestamp high
+    out.extend_from_slice(&ts_low.to_le_bytes()); // timestamp low
+    out.extend_from_slice(&(cap_len as u32).to_le_bytes()); // captured length
+    out.extend_from_slice(&(cap_len as u32).to_le_bytes()); // original length
+    out.extend_from_slice(frame);
+    out.extend(std::iter::repeat(0u8).take(pad));
+    out.extend_from_slice(&(epb_total as u32).to_le_bytes()); // total length (trailer)
+    out
+}
+
+// 매 전송의 프레임을 설정 디렉토리의 pcapng 파일에 누적 기록한다.
+// 파일이 없으면 Section Header Block + Interface Description Block을 먼저 쓰고,
+// 그 뒤에(그리고 이후 매 전송마다) Enhanced Packet Block을 하나씩 append한다.
+// 덕분에 한 파일에 여러 번의 전송이 순서대로 쌓여 Wireshark에서 모두 볼 수 있다.
+fn write_pcapng(app_handle: &AppHandle, frame: &[u8]) -> Result<std::path::PathBuf> {
+    let config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .context("애플리케이션 설정 디렉토리를 가져올 수 없습니다.")?;
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)
+            .with_context(|| format!("설정 디렉토리 생성 실패: {:?}", config_dir))?;
+    }
+    let path = config_dir.join("wol-capture.pcapng");
+
+    // 기존 파일이 비어있지 않다면 헤더는 이미 기록된 것으로 보고 EPB만 덧붙인다.
+    let header_needed = std::fs::metadata(&path).map(|m| m.len() == 0).unwrap_or(true);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("pcapng 파일 열기 실패: {:?}", path))?;
+
+    if header_needed {
+        file.write_all(&build_pcapng_header())
+            .with_context(|| format!("pcapng 헤더 쓰기 실패: {:?}", path))?;
+    }
+    file.write_all(&build_enhanced_packet_block(frame))
+        .with_context(|| format!("pcapng 파일 쓰기 실패: {:?}", path))?;
+
+    println!("WOL magic packet captured to {:?}", path);
+    Ok(path)
+}
+
+// 매직 패킷을 전송하면서 동시에 pcapng로 캡처하는 내부 로직(디버그용).
+async fn send_wol_packet_debug_internal(
+    app_handle: &AppHandle,
+    mac_address: String,
+    target_addr: Option<String>,
+) -> Result<String> {
+    // 1. 실제 전송은 기존 경로를 그대로 사용한다.
+    send_wol_packet_internal(mac_address.clone(), target_addr.clone()).await?;
+
+    // 2. 전송한 것과 동일한 매직 패킷을 합성 프레임으로 구성해 캡처한다.
+    let mac = parse_mac_bytes(&mac_address)?;
+    let payload = build_magic_packet(&mac);
+    // 목적지 IP는 브로드캐스트(255.255.255.255) 또는 지정 대상을 가능한 만큼 반영한다.
+    let dst_ip = target_addr
+        .as_deref()
+        .and_then(|a| a.parse::<std::net::Ipv4Addr>().ok())
+        .map(|v4| v4.octets())
+        .unwrap_or([255, 255, 255, 255]);
+    let frame = build_synthetic_frame(&mac, dst_ip, &payload);
+    let path = write_pcapng(app_handle, &frame)?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
 // Tauri 커맨드로 정의하여 프론트엔드(JavaScript/TypeScript)에서 호출할 수 있도록 합니다.
 // 이 함수는 프론트엔드와의 직접적인 인터페이스 역할을 합니다.
 // 내부 로직 함수(send_wol_packet_internal)를 호출하고,
@@ -58,3 +312,38 @@ pub async fn send_wol_packet(
             e.to_string() // 프론트엔드로 전달될 최종 에러 메시지 (String)
         })
 }
+
+// send_wol_packet과 동일하게 패킷을 전송하되, 보낸 매직 패킷을 설정 디렉토리의
+// pcapng 파일로도 기록하는 디버그용 Tauri 커맨드. 기록된 파일 경로를 반환한다.
+#[tauri::command]
+pub async fn send_wol_packet_debug(
+    app_handle: AppHandle,
+    mac_address: String,
+    target_addr: Option<String>,
+) -> Result<String, String> {
+    send_wol_packet_debug_internal(&app_handle, mac_address, target_addr)
+        .await
+        .map_err(|e| {
+            eprintln!("Error sending/capturing WOL packet: {:?}", e);
+            e.to_string()
+        })
+}
+
+// 매직 패킷을 보낸 뒤 대상이 깨어났는지 확인하여 WakeResult를 반환하는 Tauri 커맨드.
+// 폴링 중에는 wol://probe-progress 이벤트로 경과 시간을 프론트엔드에 알린다.
+#[tauri::command]
+pub async fn wake_and_wait(
+    app_handle: AppHandle,
+    mac_address: String,
+    target_addr: Option<String>,
+    check_addr: String,
+    port: u16,
+    timeout_secs: u64,
+) -> Result<WakeResult, String> {
+    wake_and_wait_internal(&app_handle, mac_address, target_addr, check_addr, port, timeout_secs)
+        .await
+        .map_err(|e| {
+            eprintln!("Error during wake_and_wait: {:?}", e);
+            e.to_string()
+        })
+}